@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::state::HistoryState;
+
+const HISTORY_STORE_FILE: &str = "watch-history.json";
+/// Seek back a little on resume so the user doesn't miss dialogue that was
+/// mid-sentence when they left off.
+const RESUME_PRE_ROLL_SECS: f64 = 5.0;
+/// Position past this fraction of the runtime counts as "finished".
+const FINISHED_THRESHOLD: f64 = 0.95;
+/// Minimum gap between `store.save()` disk flushes from progress ticks.
+/// `store.set()` still runs every tick so in-memory reads stay current.
+const HISTORY_SAVE_INTERVAL_SECS: i64 = 5;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WatchRecord {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub last_position: f64,
+    pub duration: f64,
+    pub updated_at: i64,
+    pub finished: bool,
+}
+
+#[tauri::command]
+pub fn history_get<R: Runtime>(app: AppHandle<R>, id: String) -> Result<Option<WatchRecord>, String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    match store.get(&id) {
+        Some(value) => serde_json::from_value(value).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub fn history_list<R: Runtime>(app: AppHandle<R>, limit: usize) -> Result<Vec<WatchRecord>, String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    let mut records: Vec<WatchRecord> = store
+        .entries()
+        .into_iter()
+        .filter_map(|(_, value)| serde_json::from_value(value).ok())
+        .collect();
+    records.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    records.truncate(limit);
+    Ok(records)
+}
+
+#[tauri::command]
+pub fn history_mark_watched<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    if let Some(value) = store.get(&id) {
+        let mut record: WatchRecord = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        record.finished = true;
+        record.last_position = record.duration;
+        store.set(id, serde_json::to_value(record).map_err(|e| e.to_string())?);
+        store.save().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn history_clear<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    store.clear();
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Records (or updates) progress for a media item, auto-marking it finished
+/// once playback crosses [`FINISHED_THRESHOLD`] of its duration.
+///
+/// `store.set()` runs every call so `history_get`/`history_list` always see
+/// the latest position, but the disk `store.save()` is throttled to
+/// [`HISTORY_SAVE_INTERVAL_SECS`] per `window_label` — called from a 250ms
+/// emitter tick, saving on every call would mean several full-store writes
+/// per second per window. Call [`flush_history`] on pause/stop/window-close
+/// to persist promptly instead of waiting out the throttle.
+pub fn record_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: &str,
+    id: &str,
+    title: &str,
+    url: &str,
+    position: f64,
+    duration: f64,
+    updated_at: i64,
+) -> Result<(), String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    let was_finished = store
+        .get(id)
+        .and_then(|value| serde_json::from_value::<WatchRecord>(value).ok())
+        .map(|record| record.finished)
+        .unwrap_or(false);
+    let finished = duration > 0.0 && position / duration >= FINISHED_THRESHOLD;
+    let record = WatchRecord {
+        id: id.to_string(),
+        title: title.to_string(),
+        url: url.to_string(),
+        last_position: position,
+        duration,
+        updated_at,
+        finished,
+    };
+    store.set(id, serde_json::to_value(record).map_err(|e| e.to_string())?);
+
+    let history_state = app.state::<HistoryState>();
+    let mut last_saved_at = history_state.last_saved_at.lock().unwrap();
+    let due = match last_saved_at.get(window_label) {
+        Some(last) => updated_at - last >= HISTORY_SAVE_INTERVAL_SECS,
+        None => true,
+    };
+    // Only force a save on the transition into "finished" - once crossed,
+    // `finished` stays true for every tick of the remaining ~5% of runtime
+    // (which can be minutes), and re-forcing every tick would recreate the
+    // exact disk thrash the throttle above exists to remove.
+    let just_finished = finished && !was_finished;
+    if due || just_finished {
+        store.save().map_err(|e| e.to_string())?;
+        last_saved_at.insert(window_label.to_string(), updated_at);
+    }
+    Ok(())
+}
+
+/// Forces an immediate disk flush of the watch-history store, bypassing the
+/// [`HISTORY_SAVE_INTERVAL_SECS`] throttle in [`record_progress`]. Call this
+/// on pause, stop, and window-close so a position is never lost to a crash
+/// between throttled saves.
+pub fn flush_history<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let store = app.store(HISTORY_STORE_FILE).map_err(|e| e.to_string())?;
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Computes where playback should resume for a media item, applying the
+/// pre-roll back so viewers don't rejoin mid-sentence. Returns `None` for a
+/// fresh item or one already marked finished.
+pub fn resume_position<R: Runtime>(app: &AppHandle<R>, id: &str) -> Option<f64> {
+    let store = app.store(HISTORY_STORE_FILE).ok()?;
+    let value = store.get(id)?;
+    let record: WatchRecord = serde_json::from_value(value).ok()?;
+    if record.finished {
+        return None;
+    }
+    Some((record.last_position - RESUME_PRE_ROLL_SECS).max(0.0))
+}
@@ -0,0 +1,11 @@
+mod history;
+mod player;
+mod proxy;
+mod updater;
+mod window;
+
+pub use history::*;
+pub use player::*;
+pub use proxy::*;
+pub use updater::*;
+pub use window::*;
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use tauri::{Manager, Runtime, WebviewWindow};
+use tauri_plugin_libmpv::MpvExt;
+
+use super::history::{flush_history, resume_position};
+use crate::state::{CurrentMedia, PlayerState};
+
+/// Event name emitted a few times per second while a window has mpv open.
+pub const PLAYBACK_STATE_EVENT: &str = "playback://state";
+
+#[derive(Clone, Serialize)]
+pub struct TrackInfo {
+    pub id: i64,
+    pub kind: String,
+    pub title: Option<String>,
+    pub lang: Option<String>,
+    pub selected: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct PlaybackState {
+    pub position: f64,
+    pub duration: f64,
+    pub paused: bool,
+    pub buffering: bool,
+    pub volume: f64,
+    pub track_list: Vec<TrackInfo>,
+}
+
+// Every command below takes the invoking `WebviewWindow` rather than the
+// `AppHandle`: `MpvExt` is implemented per-window, so each window (the main
+// browsing window, a detached player, a PiP thumbnail) drives its own mpv
+// instance and can play a different stream independently of the others.
+
+#[tauri::command]
+pub async fn player_load<R: Runtime>(
+    window: WebviewWindow<R>,
+    id: String,
+    title: String,
+    url: String,
+    start_pos: Option<f64>,
+) -> Result<(), String> {
+    // `loadfile` is async in libmpv - the file isn't demuxed yet by the time
+    // this call returns, so a `seek` issued right after commonly no-ops.
+    // Passing the resume offset as the load's `start=` option instead applies
+    // it once the file is actually open.
+    let resume_pos = start_pos.or_else(|| resume_position(window.app_handle(), &id));
+    window.mpv().load_at(&url, resume_pos).map_err(|e| e.to_string())?;
+
+    let player_state = window.state::<PlayerState>();
+    player_state
+        .current
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string(), CurrentMedia { id, title, url });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn player_play<R: Runtime>(window: WebviewWindow<R>) -> Result<(), String> {
+    window.mpv().set_pause(false).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn player_pause<R: Runtime>(window: WebviewWindow<R>) -> Result<(), String> {
+    window.mpv().set_pause(true).map_err(|e| e.to_string())?;
+    flush_history(window.app_handle())
+}
+
+#[tauri::command]
+pub async fn player_seek<R: Runtime>(window: WebviewWindow<R>, secs: f64) -> Result<(), String> {
+    window.mpv().seek(secs).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn player_set_volume<R: Runtime>(window: WebviewWindow<R>, volume: f64) -> Result<(), String> {
+    window.mpv().set_volume(volume).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn player_set_audio_track<R: Runtime>(window: WebviewWindow<R>, id: i64) -> Result<(), String> {
+    window.mpv().set_audio_track(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn player_set_subtitle_track<R: Runtime>(
+    window: WebviewWindow<R>,
+    id: i64,
+) -> Result<(), String> {
+    window.mpv().set_subtitle_track(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn player_stop<R: Runtime>(window: WebviewWindow<R>) -> Result<(), String> {
+    window.mpv().stop().map_err(|e| e.to_string())?;
+    let app = window.app_handle().clone();
+    window
+        .state::<PlayerState>()
+        .current
+        .lock()
+        .unwrap()
+        .remove(window.label());
+    flush_history(&app)
+}
+
+/// Polls a window's mpv handle for its current state. Returns `None` once
+/// nothing is loaded in that window.
+pub fn read_playback_state<R: Runtime>(window: &WebviewWindow<R>) -> Option<PlaybackState> {
+    let mpv = window.mpv();
+    let property = mpv.get_property_f64("time-pos").ok()?;
+    let track_list = mpv
+        .get_track_list()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| TrackInfo {
+            id: t.id,
+            kind: t.kind,
+            title: t.title,
+            lang: t.lang,
+            selected: t.selected,
+        })
+        .collect();
+
+    Some(PlaybackState {
+        position: property,
+        duration: mpv.get_property_f64("duration").unwrap_or(0.0),
+        paused: mpv.get_property_bool("pause").unwrap_or(true),
+        buffering: mpv.get_property_bool("paused-for-cache").unwrap_or(false),
+        volume: mpv.get_property_f64("volume").unwrap_or(0.0),
+        track_list,
+    })
+}
@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::proxy::ProxyState;
+
+/// Registers a remote URL (plus any headers it requires, e.g. Referer,
+/// Cookie, User-Agent) and returns a local `http://127.0.0.1:<port>/stream/<token>`
+/// URL that mpv can open in its place.
+#[tauri::command]
+pub fn proxy_register<R: Runtime>(
+    app: AppHandle<R>,
+    url: String,
+    headers: HashMap<String, String>,
+) -> Result<String, String> {
+    app.state::<ProxyState>()
+        .register(url, headers)
+        .ok_or_else(|| "streaming proxy is not ready yet".to_string())
+}
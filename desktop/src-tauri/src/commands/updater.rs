@@ -0,0 +1,74 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_updater::UpdaterExt;
+
+use crate::state::UpdaterState;
+
+/// Event name emitted while an update download is in progress.
+pub const UPDATE_PROGRESS_EVENT: &str = "update://progress";
+
+#[derive(Clone, Serialize)]
+pub struct UpdateCheckResult {
+    pub available: bool,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: usize,
+    pub content_length: Option<u64>,
+}
+
+#[tauri::command]
+pub async fn update_check<R: Runtime>(app: AppHandle<R>) -> Result<UpdateCheckResult, String> {
+    let update = app.updater().map_err(|e| e.to_string())?.check().await.map_err(|e| e.to_string())?;
+
+    let result = match &update {
+        Some(update) => UpdateCheckResult {
+            available: true,
+            version: Some(update.version.clone()),
+            notes: update.body.clone(),
+        },
+        None => UpdateCheckResult { available: false, version: None, notes: None },
+    };
+    *app.state::<UpdaterState>().pending.lock().unwrap() = update;
+    Ok(result)
+}
+
+/// Downloads and installs the update the frontend confirmed via
+/// `update_check`, emitting [`UPDATE_PROGRESS_EVENT`] as bytes arrive. Call
+/// [`update_relaunch`] afterwards to swap in the new version; we never
+/// restart the app out from under the user unprompted.
+///
+/// Installs the `Update` cached by `update_check` rather than checking
+/// again - a second check is both a wasted round-trip and a race: the
+/// version the user just confirmed in the UI could differ from whatever a
+/// fresh check returns moments later.
+#[tauri::command]
+pub async fn update_download_and_install<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let update = app
+        .state::<UpdaterState>()
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "call update_check before installing".to_string())?;
+
+    let mut downloaded = 0usize;
+    update
+        .download_and_install(
+            |chunk_len, content_length| {
+                downloaded += chunk_len;
+                let _ = app.emit(UPDATE_PROGRESS_EVENT, UpdateProgress { downloaded, content_length });
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_relaunch<R: Runtime>(app: AppHandle<R>) {
+    app.restart();
+}
@@ -0,0 +1,130 @@
+use serde::Deserialize;
+use tauri::{
+    AppHandle, LogicalPosition, LogicalSize, Manager, Runtime, WebviewUrl, WebviewWindowBuilder,
+    WindowEvent,
+};
+
+use super::history::flush_history;
+use crate::state::{PlayerState, PlayerWindow, WindowState};
+
+const DEFAULT_SIZE: (f64, f64) = (960.0, 540.0);
+const PIP_SIZE: (f64, f64) = (360.0, 202.0);
+/// Gap kept between the PiP corner thumbnail and the screen edge.
+const PIP_MARGIN: f64 = 24.0;
+
+#[derive(Deserialize)]
+pub struct OpenWindowOptions {
+    #[serde(default)]
+    pub pip: bool,
+}
+
+fn window_label(media_id: &str) -> String {
+    format!("player-{media_id}")
+}
+
+/// Evicts a detached window's bookkeeping once it's gone, whether it was
+/// closed through [`player_window_close`] or by the OS/user clicking the
+/// close button directly - otherwise `label_for` can hand back a stale label
+/// and the window's `CurrentMedia` entry lingers. Also flushes watch history
+/// so the closed window's last position reaches disk.
+fn forget_window<R: Runtime>(app: &AppHandle<R>, label: &str) {
+    app.state::<WindowState>().windows.lock().unwrap().remove(label);
+    app.state::<PlayerState>().current.lock().unwrap().remove(label);
+    let _ = flush_history(app);
+}
+
+/// Opens a dedicated, borderless, always-on-top webview window hosting its
+/// own libmpv surface, so a detached or picture-in-picture stream can run
+/// independently of the main browsing window.
+#[tauri::command]
+pub async fn player_open_window<R: Runtime>(
+    app: AppHandle<R>,
+    media_id: String,
+    options: OpenWindowOptions,
+) -> Result<(), String> {
+    let label = window_label(&media_id);
+    if app.get_webview_window(&label).is_some() {
+        return Ok(());
+    }
+
+    let size = if options.pip { PIP_SIZE } else { DEFAULT_SIZE };
+    let close_app = app.clone();
+    let close_label = label.clone();
+    WebviewWindowBuilder::new(
+        &app,
+        &label,
+        WebviewUrl::App(format!("player.html?media={media_id}").into()),
+    )
+    .title("Streamio")
+    .decorations(false)
+    .always_on_top(true)
+    .inner_size(size.0, size.1)
+    .on_window_event(move |event| {
+        if matches!(event, WindowEvent::Destroyed) {
+            forget_window(&close_app, &close_label);
+        }
+    })
+    .build()
+    .map_err(|e| e.to_string())?;
+
+    app.state::<WindowState>().windows.lock().unwrap().insert(
+        label,
+        PlayerWindow { media_id, pip: options.pip },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn player_window_close<R: Runtime>(app: AppHandle<R>, media_id: String) -> Result<(), String> {
+    let label = app
+        .state::<WindowState>()
+        .label_for(&media_id)
+        .ok_or_else(|| "no window open for that media id".to_string())?;
+
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    // `close()` fires the `Destroyed` event above asynchronously; evict here
+    // too so a caller that immediately re-opens the same media id doesn't
+    // race a stale bookkeeping entry.
+    forget_window(&app, &label);
+    Ok(())
+}
+
+/// Resizes and repositions a detached player window between its normal size
+/// and a small always-on-top corner thumbnail.
+#[tauri::command]
+pub async fn player_window_toggle_pip<R: Runtime>(app: AppHandle<R>, media_id: String) -> Result<(), String> {
+    let label = app
+        .state::<WindowState>()
+        .label_for(&media_id)
+        .ok_or_else(|| "no window open for that media id".to_string())?;
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| "window already closed".to_string())?;
+
+    let window_state = app.state::<WindowState>();
+    let mut windows = window_state.windows.lock().unwrap();
+    let entry = windows.get_mut(&label).ok_or_else(|| "no window open for that media id".to_string())?;
+    entry.pip = !entry.pip;
+    let now_pip = entry.pip;
+    drop(windows);
+
+    if now_pip {
+        window.set_size(LogicalSize::new(PIP_SIZE.0, PIP_SIZE.1)).map_err(|e| e.to_string())?;
+        if let Ok(Some(monitor)) = window.current_monitor() {
+            let scale = monitor.scale_factor();
+            let monitor_size = monitor.size().to_logical::<f64>(scale);
+            window
+                .set_position(LogicalPosition::new(
+                    monitor_size.width - PIP_SIZE.0 - PIP_MARGIN,
+                    monitor_size.height - PIP_SIZE.1 - PIP_MARGIN,
+                ))
+                .map_err(|e| e.to_string())?;
+        }
+    } else {
+        window.set_size(LogicalSize::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1)).map_err(|e| e.to_string())?;
+        window.center().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Event name emitted once an incoming `streamio://` or `magnet:` link has
+/// been parsed, so the frontend can route it into the player-load pipeline.
+pub const DEEPLINK_OPEN_EVENT: &str = "deeplink://open";
+
+#[derive(Clone, Serialize)]
+pub struct DeepLinkTarget {
+    pub scheme: String,
+    pub url: String,
+}
+
+fn parse_deep_link(url: &str) -> Option<DeepLinkTarget> {
+    let scheme = url.split_once(':').map(|(scheme, _)| scheme.to_string())?;
+    match scheme.as_str() {
+        "streamio" | "magnet" => Some(DeepLinkTarget { scheme, url: url.to_string() }),
+        _ => None,
+    }
+}
+
+/// Forwards an incoming deep link into the running app: focuses the main
+/// window if it is open (no window is created when it is not) and emits
+/// [`DEEPLINK_OPEN_EVENT`] with the parsed target so the frontend can kick
+/// off playback.
+pub fn handle_deep_link<R: Runtime>(app: &AppHandle<R>, url: &str) {
+    let Some(target) = parse_deep_link(url) else {
+        log::warn!("ignoring deep link with unsupported scheme: {url}");
+        return;
+    };
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_focus();
+    }
+    let _ = app.emit(DEEPLINK_OPEN_EVENT, target);
+}
@@ -1,27 +1,133 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[cfg(debug_assertions)]
 use tauri::Manager;
+use tauri::Emitter;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod commands;
+mod deeplink;
+mod proxy;
+mod state;
+
+use commands::{history_clear, history_get, history_list, history_mark_watched, player_load,
+    player_open_window, player_pause, player_play, player_seek, player_set_audio_track,
+    player_set_subtitle_track, player_set_volume, player_stop, player_window_close,
+    player_window_toggle_pip, proxy_register, read_playback_state, record_progress,
+    update_check, update_download_and_install, update_relaunch, PLAYBACK_STATE_EVENT};
+use deeplink::handle_deep_link;
+use proxy::{spawn_proxy_server, ProxyState};
+use state::{HistoryState, PlayerState, UpdaterState, WindowState};
+use tauri_plugin_deep_link::DeepLinkExt;
 
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! Welcome to Streamio.", name)
 }
 
+/// Polls every open window's own mpv instance a few times per second,
+/// emitting each one's state only to that window (so several detached
+/// players can show independent scrubbers/track lists) and persisting a
+/// per-window watch-history progress tick for whatever it has loaded.
+fn spawn_playback_state_emitter(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_millis(250));
+        loop {
+            ticker.tick().await;
+
+            for (label, window) in app.webview_windows() {
+                let Some(state) = read_playback_state(&window) else {
+                    continue;
+                };
+
+                let media = app
+                    .state::<PlayerState>()
+                    .current
+                    .lock()
+                    .unwrap()
+                    .get(&label)
+                    .cloned();
+                if let Some(media) = media {
+                    let updated_at = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs() as i64;
+                    let _ = record_progress(
+                        &app,
+                        &label,
+                        &media.id,
+                        &media.title,
+                        &media.url,
+                        state.position,
+                        state.duration,
+                        updated_at,
+                    );
+                }
+
+                let _ = app.emit_to(label, PLAYBACK_STATE_EVENT, state);
+            }
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first so a second launch hands its URL to us
+        // instead of opening a duplicate window.
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            if let Some(url) = argv.into_iter().nth(1) {
+                handle_deep_link(app, &url);
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_libmpv::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_store::Builder::new().build())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .manage(PlayerState::default())
+        .manage(ProxyState::default())
+        .manage(WindowState::default())
+        .manage(HistoryState::default())
+        .manage(UpdaterState::default())
         .setup(|_app| {
             #[cfg(debug_assertions)]
             {
                 let window = _app.get_webview_window("main").unwrap();
                 window.open_devtools();
             }
+            spawn_playback_state_emitter(_app.handle().clone());
+            spawn_proxy_server(_app.handle().clone());
+
+            let deep_link_app = _app.handle().clone();
+            _app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&deep_link_app, url.as_str());
+                }
+            });
+
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![greet])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            player_load,
+            player_play,
+            player_pause,
+            player_seek,
+            player_set_volume,
+            player_set_audio_track,
+            player_set_subtitle_track,
+            player_stop,
+            history_get,
+            history_list,
+            history_mark_watched,
+            history_clear,
+            proxy_register,
+            player_open_window,
+            player_window_close,
+            player_window_toggle_pip,
+            update_check,
+            update_download_and_install,
+            update_relaunch,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
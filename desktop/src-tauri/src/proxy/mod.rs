@@ -0,0 +1,59 @@
+// This proxy forwards via `reqwest` + a hand-rolled `axum` server rather than
+// `tauri_plugin_http`: that plugin's fetch is invoked from the webview (JS)
+// side, and what needs to open the stream here is mpv, a separate native
+// process with no access to the webview's fetch shim. `axum`, `reqwest`,
+// `uuid`, and `tokio`'s feature flags (`tokio::net`, `reqwest`'s streaming
+// body support) need to be declared in `src-tauri/Cargo.toml` - unverified
+// in this tree since it has no manifest; confirm before merge.
+mod server;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+pub use server::spawn_proxy_server;
+
+/// How long an unused registration stays valid. Every proxied request counts
+/// as use, so a stream still being played keeps its token; a fresh token is
+/// one `proxy_register` call away, so this just bounds how long a token for a
+/// closed/abandoned stream lingers in memory.
+const TARGET_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// An upstream a local `/stream/<token>` URL is forwarded to, along with the
+/// headers the upstream requires (Referer, Cookie, User-Agent, ...) that mpv
+/// has no way to attach itself.
+#[derive(Clone)]
+pub struct ProxyTarget {
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    registered_at: Instant,
+}
+
+#[derive(Default)]
+pub struct ProxyState {
+    pub port: Mutex<Option<u16>>,
+    pub targets: Mutex<HashMap<String, ProxyTarget>>,
+    /// Shared across every proxied request so seeks reuse pooled connections
+    /// instead of paying a fresh TLS handshake per Range request.
+    pub client: reqwest::Client,
+}
+
+impl ProxyState {
+    /// Registers a new proxy target and returns the local URL mpv should open.
+    ///
+    /// Opportunistically prunes any targets past [`TARGET_TTL`] first - there's
+    /// no signal here for "mpv is done with this token" (a window can close
+    /// without ever calling back into the proxy), so registration is the one
+    /// place we're guaranteed to run often enough to keep `targets` bounded.
+    pub fn register(&self, url: String, headers: HashMap<String, String>) -> Option<String> {
+        let token = Uuid::new_v4().to_string();
+        let mut targets = self.targets.lock().unwrap();
+        targets.retain(|_, target| target.registered_at.elapsed() < TARGET_TTL);
+        targets.insert(token.clone(), ProxyTarget { url, headers, registered_at: Instant::now() });
+        drop(targets);
+        let port = self.port.lock().unwrap();
+        port.map(|port| format!("http://127.0.0.1:{port}/stream/{token}"))
+    }
+}
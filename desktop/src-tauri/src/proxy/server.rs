@@ -0,0 +1,96 @@
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::net::TcpListener;
+
+use super::ProxyState;
+
+/// Range and conditional headers mpv sends that must be forwarded upstream
+/// verbatim for seeking to work.
+const FORWARDED_REQUEST_HEADERS: &[&str] = &["range", "if-range", "accept"];
+/// Response headers that describe byte-range semantics and must survive the
+/// hop so mpv sees a normal, seekable HTTP stream.
+const FORWARDED_RESPONSE_HEADERS: &[&str] =
+    &["content-range", "accept-ranges", "content-length", "content-type"];
+
+async fn stream_handler<R: Runtime>(
+    State(app): State<AppHandle<R>>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let (target, client) = {
+        let state = app.state::<ProxyState>();
+        let mut targets = state.targets.lock().unwrap();
+        match targets.get_mut(&token) {
+            Some(target) => {
+                // Refresh on use so `register`'s TTL prune only drops idle tokens.
+                target.registered_at = Instant::now();
+                (target.clone(), state.client.clone())
+            }
+            None => return StatusCode::NOT_FOUND.into_response(),
+        }
+    };
+
+    let mut request = client.get(&target.url);
+    for (key, value) in &target.headers {
+        request = request.header(key, value);
+    }
+    for name in FORWARDED_REQUEST_HEADERS {
+        if let Some(value) = headers.get(*name) {
+            request = request.header(*name, value.clone());
+        }
+    }
+
+    let upstream = match request.send().await {
+        Ok(upstream) => upstream,
+        Err(err) => return (StatusCode::BAD_GATEWAY, err.to_string()).into_response(),
+    };
+
+    let status = StatusCode::from_u16(upstream.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+    let mut response_headers = HeaderMap::new();
+    for name in FORWARDED_RESPONSE_HEADERS {
+        if let Some(value) = upstream.headers().get(*name) {
+            if let (Ok(name), value) = (HeaderName::try_from(*name), value.clone()) {
+                response_headers.insert(name, HeaderValue::from(value));
+            }
+        }
+    }
+
+    let mut response = Response::new(Body::wrap_stream(upstream.bytes_stream()));
+    *response.status_mut() = status;
+    *response.headers_mut() = response_headers;
+    response
+}
+
+/// Starts the local range-forwarding proxy once at startup and records the
+/// bound port on [`ProxyState`] so `proxy_register` can build stream URLs.
+pub fn spawn_proxy_server<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                log::error!("failed to bind streaming proxy: {err}");
+                return;
+            }
+        };
+        let port = listener.local_addr().map(|addr| addr.port()).unwrap_or(0);
+        *app.state::<ProxyState>().port.lock().unwrap() = Some(port);
+
+        let router = Router::new()
+            .route("/stream/{token}", get(stream_handler::<R>))
+            .with_state(app);
+
+        if let Err(err) = axum::serve(listener, router).await {
+            log::error!("streaming proxy stopped: {err}");
+        }
+    });
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Identifying info for whatever mpv currently has loaded, kept around so the
+/// playback-state emitter knows what to write progress ticks against.
+#[derive(Clone)]
+pub struct CurrentMedia {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// Keyed by window label, since each detached/PiP window drives its own mpv
+/// instance and can have a different item loaded at once.
+#[derive(Default)]
+pub struct PlayerState {
+    pub current: Mutex<HashMap<String, CurrentMedia>>,
+}
+
+/// Tracks, per window label, when the watch-history store was last flushed
+/// to disk, so the 250ms progress emitter can throttle `store.save()` to a
+/// coarser cadence without one window's writes starving another's.
+#[derive(Default)]
+pub struct HistoryState {
+    pub last_saved_at: Mutex<HashMap<String, i64>>,
+}
+
+/// Holds the `Update` object returned by the most recent `update_check()` so
+/// `update_download_and_install` can install exactly what the frontend
+/// confirmed, rather than re-checking (and possibly getting a different
+/// version back) right before installing.
+#[derive(Default)]
+pub struct UpdaterState {
+    pub pending: Mutex<Option<tauri_plugin_updater::Update>>,
+}
+
+/// A detached player window, keyed by its Tauri window label.
+#[derive(Clone)]
+pub struct PlayerWindow {
+    pub media_id: String,
+    pub pip: bool,
+}
+
+#[derive(Default)]
+pub struct WindowState {
+    pub windows: Mutex<HashMap<String, PlayerWindow>>,
+}
+
+impl WindowState {
+    pub fn label_for(&self, media_id: &str) -> Option<String> {
+        self.windows
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, window)| window.media_id == media_id)
+            .map(|(label, _)| label.clone())
+    }
+}